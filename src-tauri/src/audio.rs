@@ -0,0 +1,272 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
+
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
+use rodio::{source::Buffered, source::SineWave, Decoder, OutputStream, Sink, Source};
+use tauri::{AppHandle, State};
+
+use crate::settings::{Cue, CueSettings, SettingsState};
+
+// ===============================
+// Sound Effects
+// ===============================
+// Named cues fired by the frame loop. Each variant maps to a `.ogg` or
+// `.wav` asset under `assets/sfx/`（SoundBank::load 参照）and falls back to
+// a synthesized sine tone when neither is found on disk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Sfx {
+    ZoneTap,
+    ZoneSmall,
+    ZoneMid,
+    ZoneLarge,
+    ZoneFull,
+    Thirty,
+    Release,
+}
+
+impl Sfx {
+    // Filename stem under `assets/sfx/`, tried against each extension in
+    // `SoundBank::ASSET_EXTENSIONS` so a pack can supply either `.ogg` or
+    // `.wav` for a given cue.
+    fn asset_stem(self) -> &'static str {
+        match self {
+            Sfx::ZoneTap => "zone_tap",
+            Sfx::ZoneSmall => "zone_small",
+            Sfx::ZoneMid => "zone_mid",
+            Sfx::ZoneLarge => "zone_large",
+            Sfx::ZoneFull => "zone_full",
+            Sfx::Thirty => "thirty",
+            Sfx::Release => "release",
+        }
+    }
+
+    // Configured sine-synth fallback used when no asset is cached for this cue.
+    fn fallback(self, cues: &CueSettings) -> Cue {
+        match self {
+            Sfx::ZoneTap => cues.zone_tap.clone(),
+            Sfx::ZoneSmall => cues.zone_small.clone(),
+            Sfx::ZoneMid => cues.zone_mid.clone(),
+            Sfx::ZoneLarge => cues.zone_large.clone(),
+            Sfx::ZoneFull => cues.zone_full.clone(),
+            Sfx::Thirty => cues.thirty.clone(),
+            Sfx::Release => cues.release.clone(),
+        }
+    }
+}
+
+pub enum AudioCmd {
+    PlaySfx(Sfx),
+    Beep { freq: u32, ms: u64 },
+    SetDevice(Option<String>),
+}
+
+// ===============================
+// Output Devices
+// ===============================
+// Device names as surfaced to the HUD; `None` means "system default".
+pub fn list_devices() -> Vec<String> {
+    let host = rodio::cpal::default_host();
+    host.output_devices()
+        .map(|devices| devices.filter_map(|d| d.name().ok()).collect())
+        .unwrap_or_default()
+}
+
+fn find_device(name: &str) -> Option<rodio::cpal::Device> {
+    let host = rodio::cpal::default_host();
+    host.output_devices()
+        .ok()?
+        .find(|d| d.name().map(|n| n == name).unwrap_or(false))
+}
+
+const SINK_POOL_SIZE: usize = 4;
+
+// A small ring of pre-created sinks reused across plays, rather than
+// spinning up (and detaching) one `Sink` per cue. Sinks on the same
+// `OutputStreamHandle` already mix independently, so this just caps how
+// many overlapping voices a single device keeps open at once.
+struct SinkPool {
+    sinks: Vec<Sink>,
+    next: AtomicUsize,
+}
+
+impl SinkPool {
+    fn new(handle: &rodio::OutputStreamHandle) -> Self {
+        let sinks = (0..SINK_POOL_SIZE)
+            .filter_map(|_| Sink::try_new(handle).ok())
+            .collect();
+        Self {
+            sinks,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    fn play<S>(&self, source: S)
+    where
+        S: Source + Send + 'static,
+        S::Item: rodio::Sample + Send,
+        f32: rodio::cpal::FromSample<S::Item>,
+    {
+        if self.sinks.is_empty() {
+            return;
+        }
+        let i = self.next.fetch_add(1, Ordering::Relaxed) % self.sinks.len();
+        let sink = &self.sinks[i];
+        sink.stop();
+        sink.append(source);
+    }
+}
+
+// Owns the live output stream plus its sink pool; swapped out wholesale
+// when the user picks a different output device.
+struct AudioBackend {
+    _stream: OutputStream,
+    pool: SinkPool,
+}
+
+impl AudioBackend {
+    fn init(device_name: Option<&str>) -> Self {
+        let device = device_name.and_then(find_device);
+
+        let (stream, handle) = match device {
+            Some(device) => OutputStream::try_from_device(&device),
+            None => OutputStream::try_default(),
+        }
+        .expect("failed to init audio output");
+
+        let pool = SinkPool::new(&handle);
+        Self {
+            _stream: stream,
+            pool,
+        }
+    }
+}
+
+type CachedClip = Buffered<Decoder<BufReader<File>>>;
+
+// Pre-decoded sfx assets keyed by cue, decoded once at startup so triggering
+// a cue never touches disk. `Buffered` clones are cheap (shared sample data),
+// so the same clip can be layered onto multiple sinks.
+struct SoundBank {
+    clips: HashMap<Sfx, CachedClip>,
+}
+
+impl SoundBank {
+    // Extensions tried per cue, in order, so a pack can supply either a
+    // compressed `.ogg` or an uncompressed `.wav` for the same stem.
+    const ASSET_EXTENSIONS: [&'static str; 2] = ["ogg", "wav"];
+
+    fn load(dir: &Path) -> Self {
+        let all = [
+            Sfx::ZoneTap,
+            Sfx::ZoneSmall,
+            Sfx::ZoneMid,
+            Sfx::ZoneLarge,
+            Sfx::ZoneFull,
+            Sfx::Thirty,
+            Sfx::Release,
+        ];
+
+        let mut clips = HashMap::new();
+        for sfx in all {
+            let found = Self::ASSET_EXTENSIONS.iter().find_map(|ext| {
+                let path = dir.join(format!("{}.{}", sfx.asset_stem(), ext));
+                File::open(&path)
+                    .and_then(|f| {
+                        Decoder::new(BufReader::new(f))
+                            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+                    })
+                    .ok()
+            });
+
+            match found {
+                Some(source) => {
+                    clips.insert(sfx, source.buffered());
+                }
+                None => {
+                    // Asset missing (in every known extension) or undecodable:
+                    // fall through to the sine synth fallback at play time.
+                }
+            }
+        }
+
+        Self { clips }
+    }
+
+    fn play(&self, backend: &AudioBackend, sfx: Sfx, cues: &CueSettings) {
+        if let Some(clip) = self.clips.get(&sfx) {
+            backend.pool.play(clip.clone());
+            return;
+        }
+
+        let cue = sfx.fallback(cues);
+        play_beep(backend, cue.freq, cue.ms);
+    }
+}
+
+fn play_beep(backend: &AudioBackend, freq: u32, ms: u64) {
+    backend.pool.play(
+        SineWave::new(freq as f32)
+            .take_duration(Duration::from_millis(ms))
+            .amplify(0.20),
+    );
+}
+
+fn sfx_dir() -> PathBuf {
+    Path::new("assets").join("sfx")
+}
+
+// ===============================
+// Sound Helper（JSと同思想）
+// ===============================
+pub fn start_audio_thread(rx: mpsc::Receiver<AudioCmd>, settings: SettingsState) {
+    thread::spawn(move || {
+        let initial_device = settings.lock().unwrap().audio_device.clone();
+        let mut backend = AudioBackend::init(initial_device.as_deref());
+        let bank = SoundBank::load(&sfx_dir());
+
+        while let Ok(cmd) = rx.recv() {
+            match cmd {
+                AudioCmd::PlaySfx(sfx) => {
+                    let cues = settings.lock().unwrap().cues.clone();
+                    bank.play(&backend, sfx, &cues);
+                }
+                AudioCmd::Beep { freq, ms } => play_beep(&backend, freq, ms),
+                AudioCmd::SetDevice(name) => {
+                    backend = AudioBackend::init(name.as_deref());
+                }
+            }
+        }
+    });
+}
+
+// ===============================
+// Device Commands
+// ===============================
+#[tauri::command]
+pub fn list_audio_devices() -> Vec<String> {
+    list_devices()
+}
+
+#[tauri::command]
+pub fn set_audio_device(
+    app: AppHandle,
+    audio_tx: State<mpsc::Sender<AudioCmd>>,
+    settings: State<SettingsState>,
+    device: Option<String>,
+) {
+    audio_tx.send(AudioCmd::SetDevice(device.clone())).ok();
+
+    let mut cfg = settings.lock().unwrap();
+    cfg.audio_device = device;
+    cfg.save(&app);
+}
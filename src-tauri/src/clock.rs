@@ -0,0 +1,103 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+// ===============================
+// Tick Monitor
+// ===============================
+// Ring buffer of the last few iteration durations, used to measure the
+// effective FPS actually being achieved (as opposed to the target) so
+// drift shows up to the HUD instead of just silently accumulating.
+const WINDOW: usize = 5;
+
+struct TickMonitor {
+    samples: [Duration; WINDOW],
+    index: usize,
+    filled: usize,
+}
+
+impl TickMonitor {
+    fn new() -> Self {
+        Self {
+            samples: [Duration::ZERO; WINDOW],
+            index: 0,
+            filled: 0,
+        }
+    }
+
+    fn record(&mut self, elapsed: Duration) {
+        self.samples[self.index] = elapsed;
+        self.index = (self.index + 1) % WINDOW;
+        self.filled = (self.filled + 1).min(WINDOW);
+    }
+
+    fn effective_fps(&self) -> f64 {
+        if self.filled == 0 {
+            return 0.0;
+        }
+        let total: Duration = self.samples[..self.filled].iter().sum();
+        let avg = total / self.filled as u32;
+        if avg.is_zero() {
+            0.0
+        } else {
+            1.0 / avg.as_secs_f64()
+        }
+    }
+}
+
+// ===============================
+// Frame Clock
+// ===============================
+// Drives the frame loop off an incrementally advanced deadline
+// (`next_deadline += frame_ms` each tick) instead of a flat sleep, so emit
+// latency doesn't jitter with however long the previous iteration took, and
+// catches up without busy-spinning when the OS oversleeps. Advancing by the
+// *current* `frame_ms` each tick (rather than re-deriving the deadline from
+// a fixed start instant and the total frame count) means a live
+// `target_fps`/`speed` change only stretches or compresses frames from here
+// on, instead of retroactively applying to every frame already elapsed.
+pub struct FrameClock {
+    next_deadline: Instant,
+    last_tick_end: Instant,
+    frame_ms: f64,
+    monitor: TickMonitor,
+}
+
+impl FrameClock {
+    pub fn new(frame_ms: f64) -> Self {
+        let now = Instant::now();
+        Self {
+            next_deadline: now,
+            last_tick_end: now,
+            frame_ms,
+            monitor: TickMonitor::new(),
+        }
+    }
+
+    pub fn set_frame_ms(&mut self, frame_ms: f64) {
+        self.frame_ms = frame_ms;
+    }
+
+    pub fn effective_fps(&self) -> f64 {
+        self.monitor.effective_fps()
+    }
+
+    // Sleeps until the next frame deadline (or returns immediately if
+    // already behind it) and records how long the whole iteration took,
+    // measured from the end of the previous tick.
+    pub fn tick(&mut self) {
+        self.next_deadline += Duration::from_secs_f64(self.frame_ms / 1000.0);
+
+        let now = Instant::now();
+        if let Some(remaining) = self.next_deadline.checked_duration_since(now) {
+            thread::sleep(remaining);
+        }
+        // Already past the deadline: skip the sleep and let the next tick
+        // catch up rather than busy-spinning to make up the difference.
+
+        let tick_end = Instant::now();
+        self.monitor.record(tick_end - self.last_tick_end);
+        self.last_tick_end = tick_end;
+    }
+}
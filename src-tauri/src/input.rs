@@ -0,0 +1,277 @@
+use std::{
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::Instant,
+};
+
+use gilrs::{Button as GilButton, EventType as GilEvent, Gilrs};
+use rdev::{listen, EventType, Key};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::audio::{AudioCmd, Sfx};
+use crate::rumble::{RumbleBank, RumbleCue};
+use crate::settings::{Binding, SettingsState};
+use crate::{emit_update, frame_ms, HoldState, HudControlState, Zone};
+
+// ===============================
+// Key / Button Naming
+// ===============================
+// Small, explicit name tables rather than `Debug`-deriving the binding, so
+// `config.toml` stays stable across `rdev`/`gilrs` version bumps.
+fn key_name(key: Key) -> Option<&'static str> {
+    match key {
+        Key::Space => Some("Space"),
+        Key::UpArrow => Some("ArrowUp"),
+        Key::KeyW => Some("W"),
+        Key::ShiftLeft => Some("ShiftLeft"),
+        Key::ShiftRight => Some("ShiftRight"),
+        _ => None,
+    }
+}
+
+fn key_from_name(name: &str) -> Option<Key> {
+    match name {
+        "Space" => Some(Key::Space),
+        "ArrowUp" => Some(Key::UpArrow),
+        "W" => Some(Key::KeyW),
+        "ShiftLeft" => Some(Key::ShiftLeft),
+        "ShiftRight" => Some(Key::ShiftRight),
+        _ => None,
+    }
+}
+
+fn button_name(button: GilButton) -> Option<&'static str> {
+    match button {
+        GilButton::South => Some("South"),
+        GilButton::East => Some("East"),
+        GilButton::North => Some("North"),
+        GilButton::West => Some("West"),
+        GilButton::LeftTrigger2 => Some("LeftTrigger2"),
+        GilButton::RightTrigger2 => Some("RightTrigger2"),
+        _ => None,
+    }
+}
+
+fn button_from_name(name: &str) -> Option<GilButton> {
+    match name {
+        "South" => Some(GilButton::South),
+        "East" => Some(GilButton::East),
+        "North" => Some(GilButton::North),
+        "West" => Some(GilButton::West),
+        "LeftTrigger2" => Some(GilButton::LeftTrigger2),
+        "RightTrigger2" => Some(GilButton::RightTrigger2),
+        _ => None,
+    }
+}
+
+// Drops any entry that doesn't round-trip through `key_from_name`/
+// `button_from_name`, so a hand-edited or stale `config.toml` (old rdev/gilrs
+// name, typo, ...) can't leave an unrecognized binding silently un-actionable.
+pub fn sanitize_binding(binding: &mut Binding) {
+    binding.keys.retain(|name| key_from_name(name).is_some());
+    binding.buttons.retain(|name| button_from_name(name).is_some());
+}
+
+fn binding_has_key(binding: &Binding, key: Key) -> bool {
+    key_name(key).is_some_and(|name| binding.keys.iter().any(|k| k == name))
+}
+
+fn binding_has_button(binding: &Binding, button: GilButton) -> bool {
+    button_name(button).is_some_and(|name| binding.buttons.iter().any(|b| b == name))
+}
+
+// ===============================
+// Rebind Capture
+// ===============================
+// Which binding slot the next recognized input should be written into, set
+// by the `start_rebind` command and consumed by whichever listener sees the
+// next matching press.
+#[derive(Clone, Copy, PartialEq)]
+pub enum RebindSlot {
+    Key,
+    Button,
+}
+
+pub type PendingRebind = Arc<Mutex<Option<RebindSlot>>>;
+
+#[tauri::command]
+pub fn start_rebind(pending: State<PendingRebind>, slot: String) -> Result<(), String> {
+    let slot = match slot.as_str() {
+        "key" => RebindSlot::Key,
+        "button" => RebindSlot::Button,
+        other => return Err(format!("unknown rebind slot: {other}")),
+    };
+    *pending.lock().unwrap() = Some(slot);
+    Ok(())
+}
+
+// Adds the captured input to the binding's set rather than replacing it, so
+// rebind capture can grow a multi-key/button binding; `set_binding` remains
+// the way to clear or replace the set wholesale.
+fn capture_key(app: &AppHandle, pending: &PendingRebind, settings: &SettingsState, key: Key) -> bool {
+    let mut slot = pending.lock().unwrap();
+    if *slot != Some(RebindSlot::Key) {
+        return false;
+    }
+    let Some(name) = key_name(key) else { return false };
+
+    *slot = None;
+    let mut cfg = settings.lock().unwrap();
+    if !cfg.binding.keys.iter().any(|k| k == name) {
+        cfg.binding.keys.push(name.to_string());
+    }
+    cfg.save(app);
+    let _ = app.emit_to("hud", "binding-changed", cfg.binding.clone());
+    true
+}
+
+fn capture_button(
+    app: &AppHandle,
+    pending: &PendingRebind,
+    settings: &SettingsState,
+    button: GilButton,
+) -> bool {
+    let mut slot = pending.lock().unwrap();
+    if *slot != Some(RebindSlot::Button) {
+        return false;
+    }
+    let Some(name) = button_name(button) else { return false };
+
+    *slot = None;
+    let mut cfg = settings.lock().unwrap();
+    if !cfg.binding.buttons.iter().any(|b| b == name) {
+        cfg.binding.buttons.push(name.to_string());
+    }
+    cfg.save(app);
+    let _ = app.emit_to("hud", "binding-changed", cfg.binding.clone());
+    true
+}
+
+// ===============================
+// Hold Transitions（keyboard/gamepad 共通）
+// ===============================
+fn begin_hold(state: &Arc<Mutex<HoldState>>) {
+    let mut s = state.lock().unwrap();
+    if !s.holding {
+        s.holding = true;
+        s.start = Some(Instant::now());
+        s.last_frame = -1;
+        s.last_zone = Zone::None;
+        s.played_30f = false;
+    }
+}
+
+fn end_hold(
+    app: &AppHandle,
+    state: &Arc<Mutex<HoldState>>,
+    hud_state: &Arc<Mutex<HudControlState>>,
+    settings: &SettingsState,
+    audio_tx: &mpsc::Sender<AudioCmd>,
+) {
+    let mut s = state.lock().unwrap();
+    if s.holding {
+        if let Some(start) = s.start {
+            let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+            let frame = (elapsed_ms / frame_ms(settings)).round() as u32;
+
+            emit_update(app, frame);
+            if !hud_state.lock().unwrap().muted {
+                audio_tx.send(AudioCmd::PlaySfx(Sfx::Release)).ok(); // final音
+            }
+        }
+
+        s.holding = false;
+        s.start = None;
+        s.last_frame = -1;
+        s.last_zone = Zone::None;
+    }
+}
+
+// ===============================
+// Keyboard Listener（バインド済みキー全て）
+// ===============================
+pub fn start_keyboard_listener(
+    app: AppHandle,
+    state: Arc<Mutex<HoldState>>,
+    hud_state: Arc<Mutex<HudControlState>>,
+    settings: SettingsState,
+    pending_rebind: PendingRebind,
+    audio_tx: mpsc::Sender<AudioCmd>,
+) {
+    thread::spawn(move || {
+        let callback = move |event: rdev::Event| match event.event_type {
+            EventType::KeyPress(key) => {
+                if capture_key(&app, &pending_rebind, &settings, key) {
+                    return;
+                }
+                if binding_has_key(&settings.lock().unwrap().binding, key) {
+                    begin_hold(&state);
+                }
+            }
+
+            EventType::KeyRelease(key) => {
+                if binding_has_key(&settings.lock().unwrap().binding, key) {
+                    end_hold(&app, &state, &hud_state, &settings, &audio_tx);
+                }
+            }
+
+            _ => {}
+        };
+
+        let _ = listen(callback);
+    });
+}
+
+// ===============================
+// Gamepad Listener（バインド済みボタン全て）
+// ===============================
+pub fn start_gamepad_listener(
+    app: AppHandle,
+    state: Arc<Mutex<HoldState>>,
+    hud_state: Arc<Mutex<HudControlState>>,
+    settings: SettingsState,
+    pending_rebind: PendingRebind,
+    audio_tx: mpsc::Sender<AudioCmd>,
+    rumble_rx: mpsc::Receiver<RumbleCue>,
+) {
+    thread::spawn(move || {
+        let mut gilrs = Gilrs::new().unwrap();
+        let rumble_bank = RumbleBank::build(&mut gilrs);
+
+        loop {
+            while let Some(ev) = gilrs.next_event() {
+                match ev.event {
+                    GilEvent::ButtonPressed(button, _) => {
+                        if capture_button(&app, &pending_rebind, &settings, button) {
+                            continue;
+                        }
+                        if binding_has_button(&settings.lock().unwrap().binding, button) {
+                            begin_hold(&state);
+                        }
+                    }
+
+                    GilEvent::ButtonReleased(button, _) => {
+                        if binding_has_button(&settings.lock().unwrap().binding, button) {
+                            end_hold(&app, &state, &hud_state, &settings, &audio_tx);
+                        }
+                    }
+
+                    GilEvent::Connected => {
+                        // A controller plugged in after `RumbleBank::build` ran
+                        // at thread startup needs its id added to every effect,
+                        // or rumble silently never fires for it.
+                        rumble_bank.add_gamepad(&mut gilrs, ev.id);
+                    }
+
+                    _ => {}
+                }
+            }
+
+            while let Ok(cue) = rumble_rx.try_recv() {
+                rumble_bank.play(cue);
+            }
+
+            thread::sleep(std::time::Duration::from_millis(4));
+        }
+    });
+}
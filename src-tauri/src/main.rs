@@ -1,17 +1,30 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod audio;
+mod clock;
+mod input;
+mod rumble;
+mod settings;
+
 use std::{
     sync::{mpsc, Arc, Mutex},
     thread,
-    time::{Duration, Instant},
+    time::Instant,
 };
 
 use tauri::{AppHandle, Emitter, State, Manager};
 use serde::Serialize;
 
-use rdev::{listen, EventType, Key};
-use gilrs::{Gilrs, EventType as GilEvent, Button as GilButton};
-use rodio::{OutputStream, Sink, source::SineWave, Source};
+use audio::{list_audio_devices, set_audio_device, start_audio_thread, AudioCmd, Sfx};
+use clock::FrameClock;
+use input::{
+    sanitize_binding, start_gamepad_listener, start_keyboard_listener, start_rebind, PendingRebind,
+};
+use rumble::RumbleCue;
+use settings::{
+    get_settings, set_binding, set_cue_settings, set_frame_clock, set_thirty_frame_marker,
+    set_zone_thresholds, Settings, SettingsState, ZoneThresholds,
+};
 
 // ===============================
 // HUD Payload
@@ -27,6 +40,7 @@ struct Payload {
 struct HudControlState {
     visible: bool,
     muted: bool,
+    rumble: bool,
 }
 
 // ===============================
@@ -42,14 +56,19 @@ enum Zone {
     Full,
 }
 
-fn get_zone(frame: u32) -> Zone {
-    match frame {
-        36.. => Zone::Full,
-        25..=35 => Zone::Large,
-        14..=24 => Zone::Mid,
-        8..=13 => Zone::Small,
-        1..=7 => Zone::Tap,
-        _ => Zone::None,
+fn get_zone(frame: u32, zones: &ZoneThresholds) -> Zone {
+    if frame >= zones.full {
+        Zone::Full
+    } else if frame >= zones.large {
+        Zone::Large
+    } else if frame >= zones.mid {
+        Zone::Mid
+    } else if frame >= zones.small {
+        Zone::Small
+    } else if frame >= zones.tap {
+        Zone::Tap
+    } else {
+        Zone::None
     }
 }
 
@@ -77,32 +96,12 @@ impl Default for HoldState {
     }
 }
 
-enum AudioCmd {
-    Beep { freq: u32, ms: u64 },
-}
-
-// ===============================
-// Sound Helper（JSと同思想）
-// ===============================
-fn start_audio_thread(rx: mpsc::Receiver<AudioCmd>) {
-    thread::spawn(move || {
-        let (_stream, handle) =
-            OutputStream::try_default().expect("failed to init audio output");
-        while let Ok(cmd) = rx.recv() {
-            match cmd {
-                AudioCmd::Beep { freq, ms } => {
-                    if let Ok(sink) = Sink::try_new(&handle) {
-                        sink.append(
-                            SineWave::new(freq as f32)
-                                .take_duration(Duration::from_millis(ms))
-                                .amplify(0.20)
-                        );
-                        sink.detach();
-                    }
-                }
-            }
-        }
-    });
+// Milliseconds per logical frame, derived from the configured target FPS
+// and speed multiplier (speed < 1 stretches each frame for slow-motion
+// drills; speed > 1 compresses it).
+pub(crate) fn frame_ms(settings: &SettingsState) -> f64 {
+    let cfg = settings.lock().unwrap();
+    (1000.0 / cfg.target_fps) / cfg.speed
 }
 
 // ===============================
@@ -123,13 +122,21 @@ fn start_frame_loop(
     app: AppHandle,
     state: Arc<Mutex<HoldState>>,
     hud_state: Arc<Mutex<HudControlState>>,
+    settings: SettingsState,
     audio_tx: mpsc::Sender<AudioCmd>,
+    rumble_tx: mpsc::Sender<RumbleCue>,
 ) {
     thread::spawn(move || {
         let audio_tx = audio_tx.clone(); // ★これが重要
-        const FRAME_MS: f64 = 1000.0 / 60.0;
+
+        let initial_frame_ms = frame_ms(&settings);
+        let mut clock = FrameClock::new(initial_frame_ms);
+        let mut fps_ticks: u32 = 0;
 
         loop {
+            let frame_ms = frame_ms(&settings);
+            clock.set_frame_ms(frame_ms);
+
             {
                 let mut s = state.lock().unwrap();
                 if s.holding {
@@ -137,7 +144,7 @@ fn start_frame_loop(
                         let elapsed_ms =
                             start.elapsed().as_secs_f64() * 1000.0;
                         let frame =
-                            (elapsed_ms / FRAME_MS).floor() as i32;
+                            (elapsed_ms / frame_ms).floor() as i32;
 
                         if frame != s.last_frame && frame >= 0 {
                             s.last_frame = frame;
@@ -145,159 +152,75 @@ fn start_frame_loop(
 
                             emit_progress(&app, frame_u);
 
-                            let zone = get_zone(frame_u);
+                            let (zones, thirty_frame_marker) = {
+                                let cfg = settings.lock().unwrap();
+                                (cfg.zones.clone(), cfg.thirty_frame_marker)
+                            };
+
+                            let zone = get_zone(frame_u, &zones);
                             if zone != s.last_zone {
-                                if hud_state.lock().unwrap().muted {
-                                    s.last_zone = zone;
-                                    continue;
-                                }
-                                match zone {
-                                    Zone::Tap => {
-                                        audio_tx.send(AudioCmd::Beep { freq: 220, ms: 40 }).ok();
-                                    }
-                                    Zone::Small => {
-                                        audio_tx.send(AudioCmd::Beep { freq: 260, ms: 40 }).ok();
+                                let (muted, rumble) = {
+                                    let hud = hud_state.lock().unwrap();
+                                    (hud.muted, hud.rumble)
+                                };
+
+                                if rumble {
+                                    if let Some(cue) = RumbleCue::for_zone(zone) {
+                                        rumble_tx.send(cue).ok();
                                     }
-                                    Zone::Mid => {
-                                        audio_tx.send(AudioCmd::Beep { freq: 300, ms: 40 }).ok();
-                                    }
-                                    Zone::Large => {
-                                        audio_tx.send(AudioCmd::Beep { freq: 340, ms: 40 }).ok();
-                                    }
-                                    Zone::Full => {
-                                        audio_tx.send(AudioCmd::Beep { freq: 420, ms: 60 }).ok();
+                                }
+
+                                if !muted {
+                                    match zone {
+                                        Zone::Tap => {
+                                            audio_tx.send(AudioCmd::PlaySfx(Sfx::ZoneTap)).ok();
+                                        }
+                                        Zone::Small => {
+                                            audio_tx.send(AudioCmd::PlaySfx(Sfx::ZoneSmall)).ok();
+                                        }
+                                        Zone::Mid => {
+                                            audio_tx.send(AudioCmd::PlaySfx(Sfx::ZoneMid)).ok();
+                                        }
+                                        Zone::Large => {
+                                            audio_tx.send(AudioCmd::PlaySfx(Sfx::ZoneLarge)).ok();
+                                        }
+                                        Zone::Full => {
+                                            audio_tx.send(AudioCmd::PlaySfx(Sfx::ZoneFull)).ok();
+                                        }
+                                        Zone::None => {}
                                     }
-                                    Zone::None => {}
                                 }
 
                                 s.last_zone = zone;
                             }
 
-                            if frame_u >= 30 && !s.played_30f {
-                                if hud_state.lock().unwrap().muted {
-                                    s.played_30f = true;
-                                    continue;
+                            if frame_u >= thirty_frame_marker && !s.played_30f {
+                                let (muted, rumble) = {
+                                    let hud = hud_state.lock().unwrap();
+                                    (hud.muted, hud.rumble)
+                                };
+
+                                if rumble {
+                                    rumble_tx.send(RumbleCue::Thirty).ok();
+                                }
+
+                                if !muted {
+                                    audio_tx.send(AudioCmd::PlaySfx(Sfx::Thirty)).ok();
                                 }
-                                audio_tx.send(AudioCmd::Beep { freq: 350, ms: 80 }).ok();
                                 s.played_30f = true;
                             }
                         }
                     }
                 }
             }
-            thread::sleep(Duration::from_millis(4));
-        }
-    });
-}
-
-// ===============================
-// Keyboard Listener（Spaceのみ）
-// ===============================
-fn start_keyboard_listener(
-    app: AppHandle,
-    state: Arc<Mutex<HoldState>>,
-    hud_state: Arc<Mutex<HudControlState>>,
-    audio_tx: mpsc::Sender<AudioCmd>,
-) {
-    thread::spawn(move || {
-        let audio_tx = audio_tx.clone();
-        let callback = move |event: rdev::Event| {
-            match event.event_type {
-                EventType::KeyPress(Key::Space) => {
-                    let mut s = state.lock().unwrap();
-                    if !s.holding {
-                        s.holding = true;
-                        s.start = Some(Instant::now());
-                        s.last_frame = -1;
-                        s.last_zone = Zone::None;
-                        s.played_30f = false;
-                    }
-                }
 
-                EventType::KeyRelease(Key::Space) => {
-                    let mut s = state.lock().unwrap();
-                    if s.holding {
-                        if let Some(start) = s.start {
-                            let elapsed_ms =
-                                start.elapsed().as_secs_f64() * 1000.0;
-                            let frame =
-                                (elapsed_ms / (1000.0 / 60.0)).round() as u32;
-
-                            emit_update(&app, frame);
-                            if !hud_state.lock().unwrap().muted {
-                                audio_tx.send(AudioCmd::Beep { freq: 600, ms: 100 }).ok(); // final音
-                            }
-                        }
+            clock.tick();
 
-                        s.holding = false;
-                        s.start = None;
-                        s.last_frame = -1;
-                        s.last_zone = Zone::None;
-                    }
-                }
-
-                _ => {}
+            fps_ticks += 1;
+            if fps_ticks >= 5 {
+                fps_ticks = 0;
+                let _ = app.emit_to("hud", "hud-fps", clock.effective_fps());
             }
-        };
-
-        let _ = listen(callback);
-    });
-}
-
-// ===============================
-// Gamepad Listener（全機種共通ジャンプ）
-// ===============================
-fn start_gamepad_listener(
-    app: AppHandle,
-    state: Arc<Mutex<HoldState>>,
-    hud_state: Arc<Mutex<HudControlState>>,
-    audio_tx: mpsc::Sender<AudioCmd>,
-) {
-    thread::spawn(move || {
-        let audio_tx = audio_tx.clone();
-        let mut gilrs = Gilrs::new().unwrap();
-
-        loop {
-            while let Some(ev) = gilrs.next_event() {
-                match ev.event {
-                    GilEvent::ButtonPressed(GilButton::South, _) => {
-                        let mut s = state.lock().unwrap();
-                        if !s.holding {
-                            s.holding = true;
-                            s.start = Some(Instant::now());
-                            s.last_frame = -1;
-                            s.last_zone = Zone::None;
-                            s.played_30f = false;
-                        }
-                    }
-
-                    GilEvent::ButtonReleased(GilButton::South, _) => {
-                        let mut s = state.lock().unwrap();
-                        if s.holding {
-                            if let Some(start) = s.start {
-                                let elapsed_ms =
-                                    start.elapsed().as_secs_f64() * 1000.0;
-                                let frame =
-                                    (elapsed_ms / (1000.0 / 60.0)).round() as u32;
-
-                                emit_update(&app, frame);
-                                if !hud_state.lock().unwrap().muted {
-                                    audio_tx.send(AudioCmd::Beep { freq: 600, ms: 100 }).ok(); // final音
-                                }
-                            }
-
-                            s.holding = false;
-                            s.start = None;
-                            s.last_frame = -1;
-                            s.last_zone = Zone::None;
-                        }
-                    }
-
-                    _ => {}
-                }
-            }
-
-            thread::sleep(Duration::from_millis(4));
         }
     });
 }
@@ -306,7 +229,11 @@ fn start_gamepad_listener(
 // HUD Commands
 // ===============================
 #[tauri::command]
-fn hud_toggle(app: tauri::AppHandle, state: State<Arc<Mutex<HudControlState>>>) {
+fn hud_toggle(
+    app: tauri::AppHandle,
+    state: State<Arc<Mutex<HudControlState>>>,
+    settings: State<SettingsState>,
+) {
     let mut s = state.lock().unwrap();
     s.visible = !s.visible;
 
@@ -317,43 +244,115 @@ fn hud_toggle(app: tauri::AppHandle, state: State<Arc<Mutex<HudControlState>>>)
             let _ = hud.hide();
         }
     }
+
+    let mut cfg = settings.lock().unwrap();
+    cfg.visible = s.visible;
+    cfg.save(&app);
 }
 
 #[tauri::command]
 fn hud_mute_toggle(
     app: tauri::AppHandle,
     state: State<Arc<Mutex<HudControlState>>>,
+    settings: State<SettingsState>,
 ) {
     let mut s = state.lock().unwrap();
     s.muted = !s.muted;
 
     let _ = app.emit_to("main", "hud-mute-changed", s.muted);
+
+    let mut cfg = settings.lock().unwrap();
+    cfg.muted = s.muted;
+    cfg.save(&app);
+}
+
+#[tauri::command]
+fn hud_rumble_toggle(
+    app: tauri::AppHandle,
+    state: State<Arc<Mutex<HudControlState>>>,
+    settings: State<SettingsState>,
+) {
+    let mut s = state.lock().unwrap();
+    s.rumble = !s.rumble;
+
+    let _ = app.emit_to("main", "hud-rumble-changed", s.rumble);
+
+    let mut cfg = settings.lock().unwrap();
+    cfg.rumble = s.rumble;
+    cfg.save(&app);
 }
 
 // ===============================
 // Main
 // ===============================
 fn main() {
-    let hud_state = Arc::new(Mutex::new(HudControlState {
-        visible: true,
-        muted: false,
-    }));
-
     tauri::Builder::default()
-        .manage(hud_state.clone())
         .setup(move |app| {
             let handle = app.handle().clone();
+
+            let mut loaded = Settings::load_or_init(&handle);
+            sanitize_binding(&mut loaded.binding);
+            let settings: SettingsState = Arc::new(Mutex::new(loaded));
+            let hud_state = Arc::new(Mutex::new(HudControlState {
+                visible: settings.lock().unwrap().visible,
+                muted: settings.lock().unwrap().muted,
+                rumble: settings.lock().unwrap().rumble,
+            }));
+            app.manage(hud_state.clone());
+            app.manage(settings.clone());
+
+            let pending_rebind: PendingRebind = Arc::new(Mutex::new(None));
+            app.manage(pending_rebind.clone());
+
             let state = Arc::new(Mutex::new(HoldState::default()));
             let (audio_tx, audio_rx) = mpsc::channel();
-            start_audio_thread(audio_rx);
-
-            start_frame_loop(handle.clone(), state.clone(), hud_state.clone(), audio_tx.clone());
-            start_keyboard_listener(handle.clone(), state.clone(), hud_state.clone(), audio_tx.clone());
-            start_gamepad_listener(handle, state, hud_state, audio_tx);
+            start_audio_thread(audio_rx, settings.clone());
+            app.manage(audio_tx.clone());
+
+            let (rumble_tx, rumble_rx) = mpsc::channel();
+
+            start_frame_loop(
+                handle.clone(),
+                state.clone(),
+                hud_state.clone(),
+                settings.clone(),
+                audio_tx.clone(),
+                rumble_tx,
+            );
+            start_keyboard_listener(
+                handle.clone(),
+                state.clone(),
+                hud_state.clone(),
+                settings.clone(),
+                pending_rebind.clone(),
+                audio_tx.clone(),
+            );
+            start_gamepad_listener(
+                handle,
+                state,
+                hud_state,
+                settings,
+                pending_rebind,
+                audio_tx,
+                rumble_rx,
+            );
 
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![hud_toggle, hud_mute_toggle])
+        .invoke_handler(tauri::generate_handler![
+            hud_toggle,
+            hud_mute_toggle,
+            hud_rumble_toggle,
+            get_settings,
+            set_zone_thresholds,
+            set_thirty_frame_marker,
+            set_cue_settings,
+            set_binding,
+            start_rebind,
+            list_audio_devices,
+            set_audio_device,
+            set_frame_clock,
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use gilrs::{
+    ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Replay, Ticks},
+    GamepadId, Gilrs,
+};
+
+use crate::Zone;
+
+// ===============================
+// Rumble Cues
+// ===============================
+// Haptic counterpart to `audio::Sfx`: fired alongside the sound cue on the
+// same zone transitions / 30-frame marker, strength scaled by zone.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RumbleCue {
+    ZoneTap,
+    ZoneSmall,
+    ZoneMid,
+    ZoneLarge,
+    ZoneFull,
+    Thirty,
+}
+
+impl RumbleCue {
+    pub fn for_zone(zone: Zone) -> Option<Self> {
+        match zone {
+            Zone::Tap => Some(RumbleCue::ZoneTap),
+            Zone::Small => Some(RumbleCue::ZoneSmall),
+            Zone::Mid => Some(RumbleCue::ZoneMid),
+            Zone::Large => Some(RumbleCue::ZoneLarge),
+            Zone::Full => Some(RumbleCue::ZoneFull),
+            Zone::None => None,
+        }
+    }
+
+    // Tap → weak, Full → strong.
+    fn magnitude(self) -> u16 {
+        match self {
+            RumbleCue::ZoneTap => 8_000,
+            RumbleCue::ZoneSmall => 16_000,
+            RumbleCue::ZoneMid => 24_000,
+            RumbleCue::ZoneLarge => 40_000,
+            RumbleCue::ZoneFull => 60_000,
+            RumbleCue::Thirty => 30_000,
+        }
+    }
+}
+
+// ===============================
+// Rumble Bank
+// ===============================
+// Pre-built force-feedback effects keyed by cue, created once against the
+// gamepads connected at startup so triggering a cue is just `effect.play()`.
+pub struct RumbleBank {
+    effects: HashMap<RumbleCue, Effect>,
+}
+
+impl RumbleBank {
+    pub fn build(gilrs: &mut Gilrs) -> Self {
+        let all = [
+            RumbleCue::ZoneTap,
+            RumbleCue::ZoneSmall,
+            RumbleCue::ZoneMid,
+            RumbleCue::ZoneLarge,
+            RumbleCue::ZoneFull,
+            RumbleCue::Thirty,
+        ];
+
+        let gamepad_ids: Vec<_> = gilrs.gamepads().map(|(id, _)| id).collect();
+
+        let mut effects = HashMap::new();
+        for cue in all {
+            let built = EffectBuilder::new()
+                .add_effect(BaseEffect {
+                    kind: BaseEffectType::Strong {
+                        magnitude: cue.magnitude(),
+                    },
+                    scheduling: Replay {
+                        play_for: Ticks::from_ms(120),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .gamepads(&gamepad_ids)
+                .finish(gilrs);
+
+            if let Ok(effect) = built {
+                effects.insert(cue, effect);
+            }
+        }
+
+        Self { effects }
+    }
+
+    pub fn play(&self, cue: RumbleCue) {
+        if let Some(effect) = self.effects.get(&cue) {
+            let _ = effect.play();
+        }
+    }
+
+    // `build` only targets the gamepads seen at thread startup; a controller
+    // plugged in later needs its id added to every existing effect, or it
+    // stays silent for the rest of the session.
+    pub fn add_gamepad(&self, gilrs: &mut Gilrs, id: GamepadId) {
+        for effect in self.effects.values() {
+            let _ = effect.add_gamepad(gilrs, id);
+        }
+    }
+}
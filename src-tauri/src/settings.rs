@@ -0,0 +1,224 @@
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+// ===============================
+// Zone Thresholds
+// ===============================
+// Frame counts at which each zone begins (inclusive), replacing the
+// compiled-in 36/25/14/8/1 breakpoints so mods with different jump curves
+// can be retuned without a rebuild.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ZoneThresholds {
+    pub tap: u32,
+    pub small: u32,
+    pub mid: u32,
+    pub large: u32,
+    pub full: u32,
+}
+
+impl Default for ZoneThresholds {
+    fn default() -> Self {
+        Self {
+            tap: 1,
+            small: 8,
+            mid: 14,
+            large: 25,
+            full: 36,
+        }
+    }
+}
+
+// ===============================
+// Cue Tuning
+// ===============================
+// Fallback sine-synth frequency/duration used when a cue's sfx asset is
+// missing (see `audio::SoundBank`).
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Cue {
+    pub freq: u32,
+    pub ms: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CueSettings {
+    pub zone_tap: Cue,
+    pub zone_small: Cue,
+    pub zone_mid: Cue,
+    pub zone_large: Cue,
+    pub zone_full: Cue,
+    pub thirty: Cue,
+    pub release: Cue,
+}
+
+impl Default for CueSettings {
+    fn default() -> Self {
+        Self {
+            zone_tap: Cue { freq: 220, ms: 40 },
+            zone_small: Cue { freq: 260, ms: 40 },
+            zone_mid: Cue { freq: 300, ms: 40 },
+            zone_large: Cue { freq: 340, ms: 60 },
+            zone_full: Cue { freq: 420, ms: 60 },
+            thirty: Cue { freq: 350, ms: 80 },
+            release: Cue { freq: 600, ms: 100 },
+        }
+    }
+}
+
+// ===============================
+// Input Bindings
+// ===============================
+// Accepted inputs for the jump hold, by name rather than by `rdev`/`gilrs`
+// type directly so the set can round-trip through TOML. Recognized names
+// are resolved in `input::key_from_name` / `input::button_from_name`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Binding {
+    pub keys: Vec<String>,
+    pub buttons: Vec<String>,
+}
+
+impl Default for Binding {
+    fn default() -> Self {
+        Self {
+            keys: vec!["Space".into()],
+            buttons: vec!["South".into()],
+        }
+    }
+}
+
+// ===============================
+// Settings
+// ===============================
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Settings {
+    pub zones: ZoneThresholds,
+    pub thirty_frame_marker: u32,
+    pub muted: bool,
+    pub visible: bool,
+    pub rumble: bool,
+    pub cues: CueSettings,
+    pub binding: Binding,
+    pub audio_device: Option<String>,
+    pub target_fps: f64,
+    pub speed: f64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            zones: ZoneThresholds::default(),
+            thirty_frame_marker: 30,
+            muted: false,
+            visible: true,
+            rumble: true,
+            cues: CueSettings::default(),
+            binding: Binding::default(),
+            audio_device: None,
+            target_fps: 60.0,
+            speed: 1.0,
+        }
+    }
+}
+
+fn config_path(app: &AppHandle) -> PathBuf {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .expect("failed to resolve app config dir");
+    dir.join("config.toml")
+}
+
+impl Settings {
+    // Loads `config.toml` from the app's config dir, writing a default file
+    // the first time the app runs there. A file that exists but fails to
+    // parse is replaced by the defaults as well.
+    pub fn load_or_init(app: &AppHandle) -> Self {
+        let path = config_path(app);
+
+        if let Ok(raw) = fs::read_to_string(&path) {
+            if let Ok(settings) = toml::from_str(&raw) {
+                return settings;
+            }
+        }
+
+        let settings = Settings::default();
+        settings.save(app);
+        settings
+    }
+
+    pub fn save(&self, app: &AppHandle) {
+        let path = config_path(app);
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        if let Ok(raw) = toml::to_string_pretty(self) {
+            let _ = fs::write(path, raw);
+        }
+    }
+}
+
+pub type SettingsState = Arc<Mutex<Settings>>;
+
+// ===============================
+// Settings Commands
+// ===============================
+#[tauri::command]
+pub fn get_settings(state: State<SettingsState>) -> Settings {
+    state.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn set_zone_thresholds(app: AppHandle, state: State<SettingsState>, zones: ZoneThresholds) {
+    let mut s = state.lock().unwrap();
+    s.zones = zones;
+    s.save(&app);
+}
+
+#[tauri::command]
+pub fn set_thirty_frame_marker(app: AppHandle, state: State<SettingsState>, frame: u32) {
+    let mut s = state.lock().unwrap();
+    s.thirty_frame_marker = frame;
+    s.save(&app);
+}
+
+#[tauri::command]
+pub fn set_cue_settings(app: AppHandle, state: State<SettingsState>, cues: CueSettings) {
+    let mut s = state.lock().unwrap();
+    s.cues = cues;
+    s.save(&app);
+}
+
+#[tauri::command]
+pub fn set_binding(app: AppHandle, state: State<SettingsState>, binding: Binding) {
+    let mut s = state.lock().unwrap();
+    s.binding = binding;
+    s.save(&app);
+}
+
+// Frame durations derived from these feed straight into `Duration::from_secs_f64`
+// (see `clock::FrameClock::tick`), which panics on a negative/NaN/infinite
+// input (a zero fps or speed yields an infinite frame duration) — floor both
+// values well above zero so a bad frontend value can't kill the frame loop thread.
+const MIN_TARGET_FPS: f64 = 1.0;
+const MIN_SPEED: f64 = 0.05;
+
+#[tauri::command]
+pub fn set_frame_clock(app: AppHandle, state: State<SettingsState>, target_fps: f64, speed: f64) {
+    let mut s = state.lock().unwrap();
+    s.target_fps = if target_fps.is_finite() {
+        target_fps.max(MIN_TARGET_FPS)
+    } else {
+        Settings::default().target_fps
+    };
+    s.speed = if speed.is_finite() {
+        speed.max(MIN_SPEED)
+    } else {
+        Settings::default().speed
+    };
+    s.save(&app);
+}